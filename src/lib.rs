@@ -1,11 +1,13 @@
 use core::fmt::Debug;
 use std::cmp;
+use std::collections::HashMap;
 use std::str;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum LexoRankKind {
     FIGMA,
-    //ATLASIAN,
+    ATLASIAN,
+    ALPHABET,
 }
 
 impl Default for LexoRankKind {
@@ -26,150 +28,725 @@ impl Debug for dyn LexoRankStrategy {
     }
 }
 
+/// A structured diagnostic describing why a position string was rejected,
+/// carrying the offending character and its index so callers can report
+/// exactly which character broke validation.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LexoError {
+    InvalidCharacter { found: char, index: usize },
+    EmptyPosition,
+    OutOfRange,
+    Unsupported,
+}
+
+impl core::fmt::Display for LexoError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LexoError::InvalidCharacter { found, index } => {
+                write!(f, "invalid character {:?} at index {}", found, index)
+            }
+            LexoError::EmptyPosition => write!(f, "position is empty"),
+            LexoError::OutOfRange => write!(f, "position is out of range"),
+            LexoError::Unsupported => write!(f, "operation not supported by this strategy"),
+        }
+    }
+}
+
+impl std::error::Error for LexoError {}
+
 trait LexoRankStrategy {
     fn compare_positions(&self, first_pos: &str, second_pos: &str) -> cmp::Ordering;
-    fn is_valid_position(&self, pos: &str) -> bool;
-    fn position_before(&self, pos: &str) -> String;
-    fn position_after(&self, pos: &str) -> String;
-    fn position_between(&self, first_pos: &str, second_pos: &str) -> String;
+    fn validate_position(&self, pos: &str) -> Result<(), LexoError>;
+    fn is_valid_position(&self, pos: &str) -> bool {
+        self.validate_position(pos).is_ok()
+    }
+    fn position_before(&self, pos: &str) -> Result<String, LexoError>;
+    fn position_after(&self, pos: &str) -> Result<String, LexoError>;
+    fn position_between(&self, first_pos: &str, second_pos: &str) -> Result<String, LexoError>;
+
+    /// Scores how confidently `pos` fits this strategy's alphabet and
+    /// structural rules, for use by [`LexoRank::detect`]. Strategies that
+    /// do not override this score zero. `detect` only scores the built-in
+    /// FIGMA and ATLASIAN strategies; custom alphabets built via
+    /// `LexoRank::with_alphabet` are never scored.
+    fn detection_score(&self, _pos: &str) -> u32 {
+        0
+    }
+
+    /// Redistributes `ranks` onto a fresh bucket/segment so the rebalanced
+    /// ranks never collide with the originals during a migration. Only
+    /// meaningful for strategies with a notion of buckets; others return
+    /// `LexoError::Unsupported`.
+    fn rebalance(&self, _ranks: &[String]) -> Result<Vec<String>, LexoError> {
+        Err(LexoError::Unsupported)
+    }
+
+    /// The number of distinct symbols in this strategy's alphabet, i.e. the
+    /// base that `to_digits`/`digits_to_position` treat positions as numbers in.
+    fn radix(&self) -> usize;
+
+    /// Splits a validated position into a strategy-specific prefix (e.g. an
+    /// Atlassian bucket plus separator) and its digits as alphabet indices,
+    /// most significant first. Used by [`LexoRank`] to divide an interval
+    /// into equal sub-intervals for bulk position generation.
+    fn to_digits(&self, pos: &str) -> Result<(String, Vec<usize>), LexoError>;
+
+    /// The inverse of `to_digits`: reassembles a position from a prefix and
+    /// a digit sequence.
+    fn digits_to_position(&self, prefix: &str, digits: &[usize]) -> String;
 }
 
 impl LexoRank {
-    fn new(kind: LexoRankKind) -> LexoRank {
+    // The alphabet LexoRankKind::FIGMA has always used: full visible ASCII.
+    const FIGMA_START_CHAR_CODE: u8 = 32;
+    const FIGMA_END_CHAR_CODE: u8 = 126;
+
+    fn default_alphabet() -> Vec<char> {
+        (Self::FIGMA_START_CHAR_CODE..=Self::FIGMA_END_CHAR_CODE)
+            .map(|c| c as char)
+            .collect()
+    }
+
+    pub fn new(kind: LexoRankKind) -> LexoRank {
         match kind {
             LexoRankKind::FIGMA => LexoRank {
                 kind: kind,
-                lexorank_strategy: Box::new(FigmaStrategy {}),
+                lexorank_strategy: Box::new(AlphabetStrategy::new(&Self::default_alphabet())),
+            },
+            LexoRankKind::ATLASIAN => LexoRank {
+                kind: kind,
+                lexorank_strategy: Box::new(AtlassianStrategy {}),
+            },
+            LexoRankKind::ALPHABET => LexoRank {
+                kind: kind,
+                lexorank_strategy: Box::new(AlphabetStrategy::new(&Self::default_alphabet())),
             },
         }
     }
-    fn compare_positions(&self, first_pos: &str, second_pos: &str) -> cmp::Ordering {
+
+    /// Builds a `LexoRank` over a user-supplied ordered alphabet (e.g.
+    /// Crockford base-32, base-62, or a URL-safe set) instead of the
+    /// built-in strategies.
+    pub fn with_alphabet(chars: &[char]) -> LexoRank {
+        LexoRank {
+            kind: LexoRankKind::ALPHABET,
+            lexorank_strategy: Box::new(AlphabetStrategy::new(chars)),
+        }
+    }
+
+    pub fn compare_positions(&self, first_pos: &str, second_pos: &str) -> cmp::Ordering {
         self.lexorank_strategy
             .compare_positions(first_pos, second_pos)
     }
 
-    fn is_valid_position(&self, pos: &str) -> bool {
+    pub fn is_valid_position(&self, pos: &str) -> bool {
         self.lexorank_strategy.is_valid_position(pos)
     }
 
-    fn position_before(&self, pos: &str) -> String {
+    pub fn position_before(&self, pos: &str) -> Result<String, LexoError> {
         self.lexorank_strategy.position_before(pos)
     }
-    fn position_after(&self, pos: &str) -> String {
+    pub fn position_after(&self, pos: &str) -> Result<String, LexoError> {
         self.lexorank_strategy.position_after(pos)
     }
-    fn position_between(&self, first_pos: &str, second_pos: &str) -> String {
+    pub fn position_between(
+        &self,
+        first_pos: &str,
+        second_pos: &str,
+    ) -> Result<String, LexoError> {
         self.lexorank_strategy
             .position_between(first_pos, second_pos)
     }
+
+    /// Redistributes `ranks` onto a fresh bucket/segment so old and new
+    /// values never collide during a migration. Only meaningful for
+    /// strategies with a notion of buckets (e.g. `LexoRankKind::ATLASIAN`);
+    /// other kinds return `LexoError::Unsupported`.
+    pub fn rebalance(&self, ranks: &[String]) -> Result<Vec<String>, LexoError> {
+        self.lexorank_strategy.rebalance(ranks)
+    }
+
+    /// Returns `count` strictly increasing positions lying in the open
+    /// interval `(first, second)`, generated by treating the interval as a
+    /// base-N numeric range (N being the strategy's alphabet size) and
+    /// dividing it into `count + 1` equal sub-intervals. Falls back to
+    /// recursive bisection when the interval is too narrow to subdivide
+    /// evenly at any representable length.
+    pub fn positions_between(
+        &self,
+        first: &str,
+        second: &str,
+        count: usize,
+    ) -> Result<Vec<String>, LexoError> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        if let Some(positions) = self.equal_subdivide(first, second, count)? {
+            return Ok(positions);
+        }
+
+        self.bisect(first, second, count)
+    }
+
+    /// Returns `count` strictly increasing positions after `pos`, built on
+    /// the same engine as [`LexoRank::positions_between`].
+    pub fn positions_after(&self, pos: &str, count: usize) -> Result<Vec<String>, LexoError> {
+        let upper = self.position_after(pos)?;
+        self.positions_between(pos, &upper, count)
+    }
+
+    /// Returns `count` strictly increasing positions before `pos`, built on
+    /// the same engine as [`LexoRank::positions_between`].
+    pub fn positions_before(&self, pos: &str, count: usize) -> Result<Vec<String>, LexoError> {
+        let lower = self.position_before(pos)?;
+        self.positions_between(&lower, pos, count)
+    }
+
+    // Widest digit count we'll grow an interval to before giving up on
+    // equal subdivision. This alone keeps `radix.pow(len)` within a u128
+    // only for a 2-symbol alphabet; larger alphabets overflow at shorter
+    // lengths, which `digits_to_value` reports as `None`.
+    const MAX_SUBDIVIDE_WIDTH: usize = 120;
+
+    /// Attempts to place `count` equally-spaced positions in the open
+    /// interval `(first, second)` by treating both endpoints as base-N
+    /// numbers (N being the strategy's radix) padded to a common length,
+    /// and stepping evenly between them. Widens the common length one digit
+    /// at a time until there's room for `count` distinct steps. Returns
+    /// `None` if no representable length fits, signalling the caller to
+    /// fall back to recursive bisection.
+    fn equal_subdivide(
+        &self,
+        first: &str,
+        second: &str,
+        count: usize,
+    ) -> Result<Option<Vec<String>>, LexoError> {
+        let (prefix, lower_digits) = self.lexorank_strategy.to_digits(first)?;
+        let (_, upper_digits) = self.lexorank_strategy.to_digits(second)?;
+        let radix = self.lexorank_strategy.radix() as u128;
+
+        let mut len = cmp::max(lower_digits.len(), upper_digits.len());
+
+        loop {
+            if len > Self::MAX_SUBDIVIDE_WIDTH {
+                return Ok(None);
+            }
+
+            // Zero-pad both endpoints to the common length: a position is
+            // equivalent to itself followed by trailing zero digits (same
+            // as "0.12" == "0.1200" in base-10 fractions), so this keeps
+            // each endpoint's value unchanged while putting both on the
+            // same scale for comparison.
+            let (Some(lower_value), Some(upper_value)) = (
+                Self::digits_to_value(&lower_digits, len, radix, 0),
+                Self::digits_to_value(&upper_digits, len, radix, 0),
+            ) else {
+                return Ok(None);
+            };
+
+            if upper_value <= lower_value {
+                return Err(LexoError::OutOfRange);
+            }
+
+            let step = (upper_value - lower_value) / (count as u128 + 1);
+            if step == 0 {
+                len += 1;
+                continue;
+            }
+
+            let positions = (1..=count as u128)
+                .map(|i| {
+                    let mut digits = Self::value_to_digits(lower_value + step * i, len, radix);
+                    // Trailing zero digits don't change the value, and a
+                    // position may not end in the alphabet's lowest symbol.
+                    while digits.last() == Some(&0) {
+                        digits.pop();
+                    }
+                    self.lexorank_strategy.digits_to_position(&prefix, &digits)
+                })
+                .collect();
+            return Ok(Some(positions));
+        }
+    }
+
+    fn digits_to_value(digits: &[usize], len: usize, radix: u128, pad: usize) -> Option<u128> {
+        let mut value: u128 = 0;
+        for i in 0..len {
+            let digit = if i < digits.len() { digits[i] } else { pad };
+            value = value.checked_mul(radix)?.checked_add(digit as u128)?;
+        }
+        Some(value)
+    }
+
+    fn value_to_digits(mut value: u128, len: usize, radix: u128) -> Vec<usize> {
+        let mut digits = vec![0usize; len];
+        for slot in digits.iter_mut().rev() {
+            *slot = (value % radix) as usize;
+            value /= radix;
+        }
+        digits
+    }
+
+    // Fallback used when the interval can't be subdivided evenly: split at
+    // the midpoint and recurse into each half.
+    fn bisect(&self, lower: &str, upper: &str, count: usize) -> Result<Vec<String>, LexoError> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mid = self.position_between(lower, upper)?;
+        let left_count = count / 2;
+        let right_count = count - left_count - 1;
+
+        let mut positions = self.bisect(lower, &mid, left_count)?;
+        positions.push(mid.clone());
+        positions.extend(self.bisect(&mid, upper, right_count)?);
+        Ok(positions)
+    }
+
+    // Below this score a candidate isn't considered a confident match for
+    // any registered strategy.
+    const DETECTION_THRESHOLD: u32 = 10;
+
+    /// Scores `pos` against every registered strategy and returns the kind
+    /// of the best match, or `None` if nothing fits confidently enough.
+    /// Lets a `LexoRank` be built by sniffing existing data rather than
+    /// requiring the kind up front. Custom alphabets built via
+    /// [`LexoRank::with_alphabet`] are not registered for detection, since
+    /// there is no canonical alphabet to score against.
+    pub fn detect(pos: &str) -> Option<LexoRankKind> {
+        let candidates: [(LexoRankKind, Box<dyn LexoRankStrategy>); 2] = [
+            (
+                LexoRankKind::FIGMA,
+                Box::new(AlphabetStrategy::new(&Self::default_alphabet())),
+            ),
+            (LexoRankKind::ATLASIAN, Box::new(AtlassianStrategy {})),
+        ];
+
+        candidates
+            .into_iter()
+            .map(|(kind, strategy)| (kind, strategy.detection_score(pos)))
+            .filter(|(_, score)| *score >= Self::DETECTION_THRESHOLD)
+            .max_by_key(|(_, score)| *score)
+            .map(|(kind, _)| kind)
+    }
 }
 
+/// Positions over an arbitrary ordered alphabet. Positions are compared
+/// lexicographically, and midpoints are computed from alphabet *indices*
+/// rather than raw byte values, so the arithmetic never overflows
+/// regardless of how the alphabet is chosen (the default visible-ASCII
+/// range, Crockford base-32, base-62, a URL-safe set, ...).
 #[derive(Debug)]
-struct FigmaStrategy {}
+struct AlphabetStrategy {
+    alphabet: Vec<char>,
+    index: HashMap<char, usize>,
+}
 
-impl FigmaStrategy {
-    const START_CHAR_CODE: u8 = 32;
-    const END_CHAR_CODE: u8 = 126;
+impl AlphabetStrategy {
+    fn new(chars: &[char]) -> AlphabetStrategy {
+        let alphabet = chars.to_vec();
+        let index = alphabet.iter().enumerate().map(|(i, c)| (*c, i)).collect();
+        AlphabetStrategy { alphabet, index }
+    }
 
-    fn avg(a: u8, b: u8) -> u8 {
-        return (a + b) / 2;
+    fn avg(a: usize, b: usize) -> usize {
+        (a + b) / 2
     }
 }
 
-impl LexoRankStrategy for FigmaStrategy {
+impl LexoRankStrategy for AlphabetStrategy {
     fn compare_positions(&self, first_pos: &str, second_pos: &str) -> cmp::Ordering {
-        first_pos.cmp(second_pos)
+        let index_of = |c: char| self.index.get(&c).copied().unwrap_or(usize::MAX);
+        first_pos
+            .chars()
+            .map(index_of)
+            .cmp(second_pos.chars().map(index_of))
     }
-    fn is_valid_position(&self, pos: &str) -> bool {
-        // We convert to bytes since the allowed alphabet
-        // is in the visible ASCII
-        let pos_bytes = pos.as_bytes();
 
-        if (pos.is_empty()) || (pos_bytes[pos.len() - 1] == Self::START_CHAR_CODE) {
-            return false;
+    fn validate_position(&self, pos: &str) -> Result<(), LexoError> {
+        if pos.is_empty() {
+            return Err(LexoError::EmptyPosition);
         }
 
-        for c in pos_bytes {
-            // println!("{:?}", c as &u8);
-            if *c < Self::START_CHAR_CODE || *c > Self::END_CHAR_CODE {
-                return false;
+        for (i, c) in pos.chars().enumerate() {
+            if !self.index.contains_key(&c) {
+                return Err(LexoError::InvalidCharacter { found: c, index: i });
             }
         }
-        true
+
+        if pos.chars().last() == self.alphabet.first().copied() {
+            return Err(LexoError::OutOfRange);
+        }
+        Ok(())
     }
 
-    fn position_before(&self, pos: &str) -> String {
-        let pos_bytes = pos.as_bytes();
+    fn radix(&self) -> usize {
+        self.alphabet.len()
+    }
 
-        for (i, c) in pos_bytes.iter().enumerate().rev() {
-            if *c > Self::START_CHAR_CODE + 1 {
-                let position = pos[0..i].to_string() + str::from_utf8(&[(*c - 1)]).unwrap();
-                return position;
+    fn to_digits(&self, pos: &str) -> Result<(String, Vec<usize>), LexoError> {
+        self.validate_position(pos)?;
+        let digits = pos.chars().map(|c| self.index[&c]).collect();
+        Ok((String::new(), digits))
+    }
+
+    fn digits_to_position(&self, _prefix: &str, digits: &[usize]) -> String {
+        digits.iter().map(|d| self.alphabet[*d]).collect()
+    }
+
+    fn position_before(&self, pos: &str) -> Result<String, LexoError> {
+        self.validate_position(pos)?;
+        let chars: Vec<char> = pos.chars().collect();
+        let min_index = 0;
+        let max_index = self.alphabet.len() - 1;
+
+        for i in (0..chars.len()).rev() {
+            let idx = self.index[&chars[i]];
+            if idx > min_index + 1 {
+                let mut position: String = chars[0..i].iter().collect();
+                position.push(self.alphabet[idx - 1]);
+                return Ok(position);
             }
         }
 
-        let position = pos[0..pos.len() - 1].to_string()
-            + str::from_utf8(&[Self::START_CHAR_CODE]).unwrap()
-            + str::from_utf8(&[Self::END_CHAR_CODE]).unwrap();
+        let mut position: String = chars[0..chars.len() - 1].iter().collect();
+        position.push(self.alphabet[min_index]);
+        position.push(self.alphabet[max_index]);
+        Ok(position)
+    }
+
+    fn position_after(&self, pos: &str) -> Result<String, LexoError> {
+        self.validate_position(pos)?;
+        let chars: Vec<char> = pos.chars().collect();
+        let max_index = self.alphabet.len() - 1;
 
-        return position;
+        for i in (0..chars.len()).rev() {
+            let idx = self.index[&chars[i]];
+            if idx < max_index {
+                let mut position: String = chars[0..i].iter().collect();
+                position.push(self.alphabet[idx + 1]);
+                return Ok(position);
+            }
+        }
+
+        let mut position: String = chars.iter().collect();
+        position.push(self.alphabet[1]);
+        Ok(position)
     }
 
-    fn position_after(&self, pos: &str) -> String {
+    fn position_between(
+        &self,
+        first_pos: &str,
+        second_pos: &str,
+    ) -> Result<String, LexoError> {
+        self.validate_position(first_pos)?;
+        self.validate_position(second_pos)?;
+
+        let min_index = 0;
+        let max_index = self.alphabet.len() - 1;
+        let first_chars: Vec<char> = first_pos.chars().collect();
+        let second_chars: Vec<char> = second_pos.chars().collect();
+        let max_len = cmp::max(first_chars.len(), second_chars.len());
+
+        let mut flag = false;
+        let mut position = String::new();
+
+        for i in 0..max_len {
+            let lower = if i < first_chars.len() {
+                self.index[&first_chars[i]]
+            } else {
+                min_index
+            };
+            let upper = if i < second_chars.len() && !flag {
+                self.index[&second_chars[i]]
+            } else {
+                max_index
+            };
+
+            if lower == upper {
+                position.push(self.alphabet[lower]);
+            } else if upper - lower > 1 {
+                position.push(self.alphabet[Self::avg(lower, upper)]);
+                flag = false;
+                break;
+            } else {
+                position.push(self.alphabet[lower]);
+                flag = true;
+            }
+        }
+
+        if flag {
+            position.push(self.alphabet[Self::avg(min_index, max_index)]);
+        }
+
+        Ok(position)
+    }
+
+    fn detection_score(&self, pos: &str) -> u32 {
+        if !self.is_valid_position(pos) {
+            return 0;
+        }
+        // A `|` separator is a strong signal this is actually an Atlassian
+        // bucket|rank key rather than a raw visible-ASCII position. Only
+        // reachable via `LexoRank::detect`'s own FIGMA-range candidate;
+        // `with_alphabet` instances are never registered for detection.
+        if pos.contains('|') {
+            20
+        } else {
+            50
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AtlassianStrategy {}
+
+impl AtlassianStrategy {
+    const BUCKET_CHARS: [u8; 3] = [b'0', b'1', b'2'];
+    const RANK_ALPHABET: &'static [u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    const MIN_DIGIT: u8 = 0;
+    const MAX_DIGIT: u8 = 35;
+
+    fn digit_value(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'z' => Some(c - b'a' + 10),
+            _ => None,
+        }
+    }
+
+    fn digit_char(v: u8) -> u8 {
+        if v < 10 {
+            b'0' + v
+        } else {
+            b'a' + (v - 10)
+        }
+    }
+
+    fn next_bucket_digit(bucket: u8) -> u8 {
+        match bucket {
+            b'0' => b'1',
+            b'1' => b'2',
+            _ => b'0',
+        }
+    }
+
+    // Splits "<bucket>|<rank>" into its bucket byte and rank body.
+    fn split(pos: &str) -> Option<(u8, &str)> {
         let pos_bytes = pos.as_bytes();
-        for (i, c) in pos_bytes.iter().enumerate().rev() {
-            if *c < Self::END_CHAR_CODE {
-                let position = pos[0..i].to_string() + str::from_utf8(&[(*c + 1)]).unwrap();
-                return position;
+        if pos_bytes.len() < 2 || pos_bytes[1] != b'|' {
+            return None;
+        }
+        if !Self::BUCKET_CHARS.contains(&pos_bytes[0]) {
+            return None;
+        }
+        Some((pos_bytes[0], &pos[2..]))
+    }
+
+}
+
+impl LexoRankStrategy for AtlassianStrategy {
+    fn compare_positions(&self, first_pos: &str, second_pos: &str) -> cmp::Ordering {
+        let first_rank = Self::split(first_pos).map(|(_, rank)| rank).unwrap_or(first_pos);
+        let second_rank = Self::split(second_pos).map(|(_, rank)| rank).unwrap_or(second_pos);
+        first_rank.cmp(second_rank)
+    }
+
+    fn validate_position(&self, pos: &str) -> Result<(), LexoError> {
+        if pos.is_empty() {
+            return Err(LexoError::EmptyPosition);
+        }
+
+        let rank = match Self::split(pos) {
+            Some((bucket, rank)) => {
+                if !Self::BUCKET_CHARS.contains(&bucket) {
+                    return Err(LexoError::InvalidCharacter {
+                        found: bucket as char,
+                        index: 0,
+                    });
+                }
+                rank
+            }
+            None => return Err(LexoError::OutOfRange),
+        };
+
+        if rank.is_empty() {
+            return Err(LexoError::EmptyPosition);
+        }
+
+        for (i, c) in rank.chars().enumerate() {
+            let valid = c.is_ascii() && Self::digit_value(c as u8).is_some();
+            if !valid {
+                return Err(LexoError::InvalidCharacter {
+                    found: c,
+                    index: i + 2,
+                });
             }
         }
-        let position = pos.to_string() + str::from_utf8(&[(Self::START_CHAR_CODE + 1)]).unwrap();
-        return position;
+        Ok(())
     }
 
-    fn position_between(&self, first_pos: &str, second_pos: &str) -> String {
+    fn radix(&self) -> usize {
+        Self::RANK_ALPHABET.len()
+    }
+
+    fn to_digits(&self, pos: &str) -> Result<(String, Vec<usize>), LexoError> {
+        self.validate_position(pos)?;
+        let (bucket, rank) = Self::split(pos).unwrap();
+        let digits = rank
+            .bytes()
+            .map(|c| Self::digit_value(c).unwrap() as usize)
+            .collect();
+        Ok((format!("{}|", bucket as char), digits))
+    }
+
+    fn digits_to_position(&self, prefix: &str, digits: &[usize]) -> String {
+        let rank: String = digits
+            .iter()
+            .map(|d| Self::digit_char(*d as u8) as char)
+            .collect();
+        format!("{}{}", prefix, rank)
+    }
+
+    fn position_before(&self, pos: &str) -> Result<String, LexoError> {
+        self.validate_position(pos)?;
+        let (bucket, rank) = Self::split(pos).unwrap();
+        let rank_bytes = rank.as_bytes();
+
+        for (i, c) in rank_bytes.iter().enumerate().rev() {
+            let v = Self::digit_value(*c).unwrap_or(Self::MIN_DIGIT);
+            if v > Self::MIN_DIGIT {
+                let position = rank[0..i].to_string()
+                    + str::from_utf8(&[Self::digit_char(v - 1)]).unwrap();
+                return Ok(format!("{}|{}", bucket as char, position));
+            }
+        }
+
+        let position = rank.to_string()
+            + str::from_utf8(&[Self::digit_char(Self::MAX_DIGIT)]).unwrap();
+        Ok(format!("{}|{}", bucket as char, position))
+    }
+
+    fn position_after(&self, pos: &str) -> Result<String, LexoError> {
+        self.validate_position(pos)?;
+        let (bucket, rank) = Self::split(pos).unwrap();
+        let rank_bytes = rank.as_bytes();
+
+        for (i, c) in rank_bytes.iter().enumerate().rev() {
+            let v = Self::digit_value(*c).unwrap_or(Self::MIN_DIGIT);
+            if v < Self::MAX_DIGIT {
+                let position = rank[0..i].to_string()
+                    + str::from_utf8(&[Self::digit_char(v + 1)]).unwrap();
+                return Ok(format!("{}|{}", bucket as char, position));
+            }
+        }
+
+        let position = rank.to_string()
+            + str::from_utf8(&[Self::digit_char(Self::MIN_DIGIT + 1)]).unwrap();
+        Ok(format!("{}|{}", bucket as char, position))
+    }
+
+    fn position_between(
+        &self,
+        first_pos: &str,
+        second_pos: &str,
+    ) -> Result<String, LexoError> {
+        self.validate_position(first_pos)?;
+        self.validate_position(second_pos)?;
+        let (bucket, first_rank) = Self::split(first_pos).unwrap();
+        let (_, second_rank) = Self::split(second_pos).unwrap();
+
         let mut flag = false;
-        let mut position = String::new();
-        let first_pos_len = first_pos.len();
-        let second_pos_len = second_pos.len();
-        let first_pos_bytes = first_pos.as_bytes();
-        let second_pos_bytes = second_pos.as_bytes();
-        let max_len = cmp::max(first_pos_len, second_pos_len);
+        let mut rank = String::new();
+        let first_rank_len = first_rank.len();
+        let second_rank_len = second_rank.len();
+        let first_rank_bytes = first_rank.as_bytes();
+        let second_rank_bytes = second_rank.as_bytes();
+        let max_len = cmp::max(first_rank_len, second_rank_len);
 
         for i in 0..max_len {
-            let lower = if i < first_pos_len {
-                first_pos_bytes[i]
+            let lower = if i < first_rank_len {
+                Self::digit_value(first_rank_bytes[i]).unwrap_or(Self::MIN_DIGIT)
             } else {
-                Self::START_CHAR_CODE
+                Self::MIN_DIGIT
             };
-            let upper = if i < second_pos_len && !flag {
-                second_pos_bytes[i]
+            let upper = if i < second_rank_len && !flag {
+                Self::digit_value(second_rank_bytes[i]).unwrap_or(Self::MAX_DIGIT)
             } else {
-                Self::END_CHAR_CODE
+                Self::MAX_DIGIT
             };
             if lower == upper {
-                position += str::from_utf8(&[lower]).unwrap();
+                rank += str::from_utf8(&[Self::digit_char(lower)]).unwrap();
             } else if upper - lower > 1 {
-                position += str::from_utf8(&[Self::avg(lower, upper)]).unwrap();
+                rank += str::from_utf8(&[Self::digit_char((lower + upper) / 2)]).unwrap();
                 flag = false;
                 break;
             } else {
-                position += str::from_utf8(&[lower]).unwrap();
+                rank += str::from_utf8(&[Self::digit_char(lower)]).unwrap();
                 flag = true;
             }
         }
 
         if flag {
-            position +=
-                str::from_utf8(&[Self::avg(Self::START_CHAR_CODE, Self::END_CHAR_CODE)]).unwrap();
+            rank += str::from_utf8(&[Self::digit_char((Self::MIN_DIGIT + Self::MAX_DIGIT) / 2)])
+                .unwrap();
+        }
+
+        Ok(format!("{}|{}", bucket as char, rank))
+    }
+
+    fn detection_score(&self, pos: &str) -> u32 {
+        if self.is_valid_position(pos) {
+            90
+        } else {
+            0
+        }
+    }
+
+    /// Redistributes `ranks` evenly across the rank alphabet under the next
+    /// bucket, so the rebalanced ranks never collide with the originals
+    /// while callers migrate records over from the old bucket. Fails
+    /// rather than guessing when the current bucket can't be determined
+    /// from `ranks`.
+    fn rebalance(&self, ranks: &[String]) -> Result<Vec<String>, LexoError> {
+        if ranks.is_empty() {
+            return Ok(Vec::new());
         }
-        return position;
+
+        let (bucket, _) = Self::split(&ranks[0]).ok_or(LexoError::OutOfRange)?;
+        let next_bucket = Self::next_bucket_digit(bucket);
+
+        let count = ranks.len() as u128;
+        let alphabet_len = Self::RANK_ALPHABET.len() as u128;
+        let mut width: u32 = 1;
+        while alphabet_len.pow(width) <= count {
+            width += 1;
+        }
+        let space = alphabet_len.pow(width);
+        let step = space / (count + 1);
+
+        let rebalanced = (1..=count)
+            .map(|i| {
+                let mut value = step * i;
+                let mut digits = vec![0u8; width as usize];
+                for slot in digits.iter_mut().rev() {
+                    *slot = (value % alphabet_len) as u8;
+                    value /= alphabet_len;
+                }
+                let rank: String = digits.iter().map(|d| Self::digit_char(*d) as char).collect();
+                format!("{}|{}", next_bucket as char, rank)
+            })
+            .collect();
+
+        Ok(rebalanced)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{LexoRank, LexoRankKind};
+    use super::{LexoError, LexoRank, LexoRankKind};
     use std::cmp;
 
     #[test]
@@ -183,6 +760,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compare_positions_out_of_alphabet_character() {
+        let lexrank = LexoRank::new(LexoRankKind::FIGMA);
+        // '\n' is outside the FIGMA alphabet (ASCII 32-126); compare_positions
+        // must degrade gracefully instead of panicking on the missing index.
+        assert_eq!(
+            lexrank.compare_positions("AB", "A\n"),
+            cmp::Ordering::Less
+        );
+        assert_eq!(
+            lexrank.compare_positions("A\n", "A\n"),
+            cmp::Ordering::Equal
+        );
+    }
+
     #[test]
     fn test_is_valid_position() {
         let lexrank = LexoRank::new(LexoRankKind::FIGMA);
@@ -195,21 +787,236 @@ mod tests {
     #[test]
     fn test_position_before() {
         let lexrank = LexoRank::new(LexoRankKind::FIGMA);
-        assert_eq!(lexrank.position_before("C"), "B");
-        assert_eq!(lexrank.position_before("AA"), "A@");
-        assert_eq!(lexrank.position_before("!"), " ~");
+        assert_eq!(lexrank.position_before("C").unwrap(), "B");
+        assert_eq!(lexrank.position_before("AA").unwrap(), "A@");
+        assert_eq!(lexrank.position_before("!").unwrap(), " ~");
     }
     #[test]
     fn test_position_after() {
         let lexrank = LexoRank::new(LexoRankKind::FIGMA);
-        assert_eq!(lexrank.position_after("C"), "D");
-        assert_eq!(lexrank.position_after("AA"), "AB");
-        assert_eq!(lexrank.position_after("~"), "~!");
+        assert_eq!(lexrank.position_after("C").unwrap(), "D");
+        assert_eq!(lexrank.position_after("AA").unwrap(), "AB");
+        assert_eq!(lexrank.position_after("~").unwrap(), "~!");
     }
     #[test]
     fn test_position_between() {
         let lexrank = LexoRank::new(LexoRankKind::FIGMA);
-        assert_eq!(lexrank.position_between("A", "C"), "B");
-        assert_eq!(lexrank.position_between("AA", "AB"), "AAO");
+        assert_eq!(lexrank.position_between("A", "C").unwrap(), "B");
+        assert_eq!(lexrank.position_between("AA", "AB").unwrap(), "AAO");
+    }
+
+    #[test]
+    fn test_position_before_invalid_input() {
+        let lexrank = LexoRank::new(LexoRankKind::FIGMA);
+        assert_eq!(lexrank.position_before(""), Err(LexoError::EmptyPosition));
+        assert_eq!(
+            lexrank.position_before("¡"),
+            Err(LexoError::InvalidCharacter {
+                found: '¡',
+                index: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_atlassian_compare_positions() {
+        let lexrank = LexoRank::new(LexoRankKind::ATLASIAN);
+        assert_eq!(
+            lexrank.compare_positions("0|aa", "0|ab"),
+            cmp::Ordering::Less
+        );
+        assert_eq!(
+            lexrank.compare_positions("0|aa", "0|aa"),
+            cmp::Ordering::Equal
+        );
+        // Bucket digit is ignored; only the rank after `|` is compared.
+        assert_eq!(
+            lexrank.compare_positions("1|aa", "0|ab"),
+            cmp::Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_atlassian_is_valid_position() {
+        let lexrank = LexoRank::new(LexoRankKind::ATLASIAN);
+        assert_eq!(lexrank.is_valid_position("0|aa"), true);
+        assert_eq!(lexrank.is_valid_position("2|z0"), true);
+        // Bucket digit out of range
+        assert_eq!(lexrank.is_valid_position("3|aa"), false);
+        // Missing separator
+        assert_eq!(lexrank.is_valid_position("0aa"), false);
+        // Rank char outside base-36 alphabet
+        assert_eq!(lexrank.is_valid_position("0|a!"), false);
+        // Empty rank body
+        assert_eq!(lexrank.is_valid_position("0|"), false);
+    }
+
+    #[test]
+    fn test_atlassian_position_between() {
+        let lexrank = LexoRank::new(LexoRankKind::ATLASIAN);
+        assert_eq!(lexrank.position_between("0|a", "0|c").unwrap(), "0|b");
+        assert_eq!(lexrank.position_between("0|aa", "0|ab").unwrap(), "0|aah");
+    }
+
+    #[test]
+    fn test_atlassian_position_between_invalid_input() {
+        let lexrank = LexoRank::new(LexoRankKind::ATLASIAN);
+        assert_eq!(
+            lexrank.position_between("0aa", "0|ab"),
+            Err(LexoError::OutOfRange)
+        );
+        assert_eq!(
+            lexrank.position_between("0|a!", "0|ab"),
+            Err(LexoError::InvalidCharacter {
+                found: '!',
+                index: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_atlassian_rebalance() {
+        let lexrank = LexoRank::new(LexoRankKind::ATLASIAN);
+        let ranks = vec!["0|a".to_string(), "0|m".to_string(), "0|z".to_string()];
+        let rebalanced = lexrank.rebalance(&ranks).unwrap();
+
+        assert_eq!(rebalanced.len(), ranks.len());
+        // Bucket flips so old and new ranks can coexist during migration.
+        for rank in &rebalanced {
+            assert!(rank.starts_with("1|"));
+        }
+        // Output stays strictly increasing.
+        for pair in rebalanced.windows(2) {
+            assert!(pair[0] < pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_atlassian_rebalance_unparsable_first_rank() {
+        let lexrank = LexoRank::new(LexoRankKind::ATLASIAN);
+        let ranks = vec!["not-a-rank".to_string(), "0|m".to_string()];
+        assert_eq!(lexrank.rebalance(&ranks), Err(LexoError::OutOfRange));
+    }
+
+    #[test]
+    fn test_rebalance_unsupported_by_other_strategies() {
+        let lexrank = LexoRank::new(LexoRankKind::FIGMA);
+        assert_eq!(
+            lexrank.rebalance(&["AA".to_string()]),
+            Err(LexoError::Unsupported)
+        );
+    }
+
+    fn crockford_base32() -> Vec<char> {
+        "0123456789ABCDEFGHJKMNPQRSTVWXYZ".chars().collect()
+    }
+
+    #[test]
+    fn test_alphabet_is_valid_position() {
+        let lexrank = LexoRank::with_alphabet(&crockford_base32());
+        assert_eq!(lexrank.is_valid_position("AA"), true);
+        // Last char equal to the alphabet's first (lowest) char is invalid
+        assert_eq!(lexrank.is_valid_position("A0"), false);
+        // Character outside the supplied alphabet
+        assert_eq!(lexrank.is_valid_position("AI"), false);
+    }
+
+    #[test]
+    fn test_alphabet_invalid_character_preserves_codepoint() {
+        // Regression: the offending character must be reported as-is, even
+        // when its codepoint doesn't fit in a byte.
+        let lexrank = LexoRank::with_alphabet(&['a', 'b', 'c']);
+        assert_eq!(
+            lexrank.position_before("a\u{3a9}"),
+            Err(LexoError::InvalidCharacter {
+                found: '\u{3a9}',
+                index: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_alphabet_position_before() {
+        let lexrank = LexoRank::with_alphabet(&crockford_base32());
+        assert_eq!(lexrank.position_before("C").unwrap(), "B");
+        assert_eq!(lexrank.position_before("AA").unwrap(), "A9");
+    }
+
+    #[test]
+    fn test_alphabet_position_after() {
+        let lexrank = LexoRank::with_alphabet(&crockford_base32());
+        assert_eq!(lexrank.position_after("C").unwrap(), "D");
+        assert_eq!(lexrank.position_after("AA").unwrap(), "AB");
+    }
+
+    #[test]
+    fn test_alphabet_position_between() {
+        let lexrank = LexoRank::with_alphabet(&crockford_base32());
+        assert_eq!(lexrank.position_between("A", "C").unwrap(), "B");
+    }
+
+    #[test]
+    fn test_positions_between() {
+        let lexrank = LexoRank::new(LexoRankKind::FIGMA);
+        let positions = lexrank.positions_between("A", "Z", 5).unwrap();
+
+        assert_eq!(positions.len(), 5);
+        let mut sorted = positions.clone();
+        sorted.sort();
+        assert_eq!(positions, sorted);
+        for pos in &positions {
+            assert_eq!(lexrank.compare_positions("A", pos), cmp::Ordering::Less);
+            assert_eq!(lexrank.compare_positions(pos, "Z"), cmp::Ordering::Less);
+        }
+    }
+
+    #[test]
+    fn test_positions_between_bulk_insert_uses_numeric_subdivision() {
+        // A 1000-item bulk insert divides the base-95 numeric range
+        // directly into equal steps, so every key stays a few characters
+        // long.
+        let lexrank = LexoRank::new(LexoRankKind::FIGMA);
+        let positions = lexrank.positions_between("A", "Z", 1000).unwrap();
+
+        assert_eq!(positions.len(), 1000);
+        let mut sorted = positions.clone();
+        sorted.sort();
+        assert_eq!(positions, sorted);
+        assert!(positions.windows(2).all(|w| w[0] != w[1]));
+        for pos in &positions {
+            assert_eq!(lexrank.compare_positions("A", pos), cmp::Ordering::Less);
+            assert_eq!(lexrank.compare_positions(pos, "Z"), cmp::Ordering::Less);
+            assert!(pos.len() <= 3);
+        }
+    }
+
+    #[test]
+    fn test_positions_after() {
+        let lexrank = LexoRank::new(LexoRankKind::FIGMA);
+        let positions = lexrank.positions_after("M", 3).unwrap();
+
+        assert_eq!(positions.len(), 3);
+        for pos in &positions {
+            assert_eq!(lexrank.compare_positions("M", pos), cmp::Ordering::Less);
+        }
+    }
+
+    #[test]
+    fn test_positions_before() {
+        let lexrank = LexoRank::new(LexoRankKind::FIGMA);
+        let positions = lexrank.positions_before("M", 3).unwrap();
+
+        assert_eq!(positions.len(), 3);
+        for pos in &positions {
+            assert_eq!(lexrank.compare_positions(pos, "M"), cmp::Ordering::Less);
+        }
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(LexoRank::detect("AB"), Some(LexoRankKind::FIGMA));
+        assert_eq!(LexoRank::detect("0|ab"), Some(LexoRankKind::ATLASIAN));
+        assert_eq!(LexoRank::detect("2|z0"), Some(LexoRankKind::ATLASIAN));
+        assert_eq!(LexoRank::detect(""), None);
     }
 }